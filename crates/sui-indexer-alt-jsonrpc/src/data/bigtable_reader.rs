@@ -0,0 +1,56 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use sui_kvstore::{BigTableClient, KeyValueStoreReader as _};
+use sui_types::{object::Object, storage::ObjectKey};
+
+use super::error::Error;
+
+/// A reader over the Bigtable key-value store, used as the cold-storage backend for object reads.
+#[derive(Clone)]
+pub(crate) struct BigtableReader {
+    client: BigTableClient,
+}
+
+impl BigtableReader {
+    pub(crate) fn new(client: BigTableClient) -> Self {
+        Self { client }
+    }
+
+    /// Read the objects named by `keys` at their exact `(id, version)`. Keys with no matching row
+    /// are simply absent from the returned vector.
+    pub(crate) async fn objects(&self, keys: &[ObjectKey]) -> Result<Vec<Object>, Arc<Error>> {
+        if keys.is_empty() {
+            return Ok(vec![]);
+        }
+
+        self.client
+            .clone()
+            .get_objects(keys)
+            .await
+            .map_err(|e| Arc::new(Error::BigtableRead(e)))
+    }
+
+    /// Read, for each key, the object at the greatest version at or below the key's version,
+    /// treating that version as an inclusive ceiling rather than an exact match. An object's
+    /// versions are stored in descending order under its id, so each ceiling resolves to a reverse
+    /// range scan bounded by the id that takes the first (highest) row at or below the ceiling.
+    /// Keys with no version at or below their ceiling are absent from the returned vector.
+    pub(crate) async fn objects_le(&self, keys: &[ObjectKey]) -> Result<Vec<Object>, Arc<Error>> {
+        let mut objects = Vec::with_capacity(keys.len());
+        for ObjectKey(id, bound) in keys {
+            let found = self
+                .client
+                .clone()
+                .reversed_scan_objects(*id, *bound, 1)
+                .await
+                .map_err(|e| Arc::new(Error::BigtableRead(e)))?;
+
+            objects.extend(found.into_iter().next());
+        }
+
+        Ok(objects)
+    }
+}
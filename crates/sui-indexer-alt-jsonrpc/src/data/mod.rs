@@ -0,0 +1,10 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+pub(crate) mod bigtable_reader;
+pub(crate) mod error;
+pub(crate) mod object_info;
+pub(crate) mod object_versions;
+pub(crate) mod objects;
+pub(crate) mod pg_reader;
+pub(crate) mod repair;
@@ -1,13 +1,17 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use anyhow::Context as _;
 use async_graphql::dataloader::Loader;
 use diesel::{BoolExpressionMethods, ExpressionMethods, QueryDsl};
 use serde::de::DeserializeOwned;
-use sui_indexer_alt_schema::{objects::StoredObject, schema::kv_objects};
+use tracing::debug;
+use sui_indexer_alt_schema::{
+    objects::{StoredObjInfo, StoredObject},
+    schema::{kv_objects, obj_info},
+};
 use sui_types::{base_types::ObjectID, object::Object, storage::ObjectKey};
 
 use crate::context::Context;
@@ -21,6 +25,44 @@ use super::{
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) struct VersionedObjectKey(pub ObjectID, pub u64);
 
+/// Key for fetching the contents of an object "as of" a version ceiling: the greatest version of
+/// the object that is less than or equal to the bound. This resolves point-in-time reads, where
+/// the bound is typically derived from a historical checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct VersionBoundedObjectKey(pub ObjectID, pub u64);
+
+/// Key for resolving the liveness of an object together with its latest contents in one batched
+/// load (two queries for the whole batch). Unlike [VersionedObjectKey], this consults the object's latest tombstone state rather than
+/// assuming a version implies live contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct LiveObjectKey(pub ObjectID);
+
+/// The resolved liveness of an object, following an explicit-state model: the latest `object_info`
+/// record carries whether the object is owned (live) or has no owner (wrapped or deleted), rather
+/// than inferring deletion from the mere absence of contents.
+///
+/// - [Live](ObjectLiveness::Live) — the object is owned and its contents are present.
+/// - [WrappedOrDeleted](ObjectLiveness::WrappedOrDeleted) — the latest info record has no owner, so
+///   the object is no longer live even if stale contents linger in `kv_objects`.
+/// - [Pruned](ObjectLiveness::Pruned) — the object is owned per its latest info, but its contents
+///   are no longer present in `kv_objects` (they have been pruned).
+#[derive(Debug, Clone)]
+pub(crate) enum ObjectLiveness {
+    Live(Object),
+    WrappedOrDeleted,
+    Pruned,
+}
+
+/// Liveness as resolved by the [LiveObjectKey] loader, with live contents still in their stored
+/// (un-deserialized) form. [load_live_batch] deserializes the `Live` payload into an `Object`,
+/// keeping BCS decoding — and any error it raises — out of the loader's `Arc<Error>` path.
+#[derive(Debug, Clone)]
+pub(crate) enum StoredLiveness {
+    Live(StoredObject),
+    WrappedOrDeleted,
+    Pruned,
+}
+
 #[async_trait::async_trait]
 impl Loader<VersionedObjectKey> for PgReader {
     type Value = StoredObject;
@@ -70,6 +112,79 @@ impl Loader<VersionedObjectKey> for PgReader {
     }
 }
 
+#[async_trait::async_trait]
+impl Loader<VersionBoundedObjectKey> for PgReader {
+    type Value = StoredObject;
+    type Error = Arc<Error>;
+
+    async fn load(
+        &self,
+        keys: &[VersionBoundedObjectKey],
+    ) -> Result<HashMap<VersionBoundedObjectKey, StoredObject>, Self::Error> {
+        use kv_objects::dsl as o;
+
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut conn = self.connect().await.map_err(Arc::new)?;
+
+        // Select, per object, the row with the greatest version at or below the bound in a single
+        // query: `DISTINCT ON (object_id)` with the descending version ordering keeps only the
+        // rank-1 row per object (equivalent to `ROW_NUMBER() OVER (PARTITION BY object_id ORDER BY
+        // object_version DESC)` taking rank 1), and the OR-ed `object_version <= bound` predicates
+        // restrict the candidate rows to each key's ceiling.
+        let mut query = o::kv_objects
+            .distinct_on(o::object_id)
+            .order((o::object_id, o::object_version.desc()))
+            .into_boxed();
+
+        for VersionBoundedObjectKey(id, bound) in keys {
+            query = query.or_filter(
+                o::object_id
+                    .eq(id.into_bytes())
+                    .and(o::object_version.le(*bound as i64)),
+            );
+        }
+
+        let objects: Vec<StoredObject> = conn.results(query).await.map_err(Arc::new)?;
+
+        let id_to_stored: HashMap<&[u8], &StoredObject> = objects
+            .iter()
+            .map(|stored| (&stored.object_id[..], stored))
+            .collect();
+
+        let mut found: HashMap<_, _> = keys
+            .iter()
+            .filter_map(|key| {
+                let slice: &[u8] = key.0.as_ref();
+                let stored = *id_to_stored.get(slice)?;
+                // The rank-1 row is the greatest version for the object; only surface it for a key
+                // whose own ceiling it actually satisfies, so a tighter bound never receives a row
+                // above it.
+                (stored.object_version as u64 <= key.1).then(|| (*key, stored.clone()))
+            })
+            .collect();
+
+        // A tighter bound on an object whose rank-1 row overshoots it gets a query of its own.
+        for key in keys {
+            let slice: &[u8] = key.0.as_ref();
+            if found.contains_key(key) || !id_to_stored.contains_key(slice) {
+                continue;
+            }
+            let query = o::kv_objects
+                .filter(o::object_id.eq(key.0.into_bytes()))
+                .filter(o::object_version.le(key.1 as i64))
+                .order(o::object_version.desc())
+                .limit(1);
+            let rows: Vec<StoredObject> = conn.results(query).await.map_err(Arc::new)?;
+            found.extend(rows.into_iter().next().map(|stored| (*key, stored)));
+        }
+
+        Ok(found)
+    }
+}
+
 #[async_trait::async_trait]
 impl Loader<VersionedObjectKey> for BigtableReader {
     type Value = Object;
@@ -97,6 +212,252 @@ impl Loader<VersionedObjectKey> for BigtableReader {
     }
 }
 
+#[async_trait::async_trait]
+impl Loader<VersionBoundedObjectKey> for BigtableReader {
+    type Value = Object;
+    type Error = Arc<Error>;
+
+    async fn load(
+        &self,
+        keys: &[VersionBoundedObjectKey],
+    ) -> Result<HashMap<VersionBoundedObjectKey, Object>, Self::Error> {
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        // Bigtable rows are ordered by version, so each bound resolves to a reverse range scan that
+        // stops at the greatest version at or below the ceiling, rather than an exact-version read.
+        // The returned object carries its actual version, which we re-attach to the originating key
+        // below, picking independently per key so two bounds on the same id don't collide.
+        let object_keys: Vec<ObjectKey> = keys
+            .iter()
+            .map(|key| ObjectKey(key.0, key.1.into()))
+            .collect();
+
+        let mut by_id: HashMap<ObjectID, Vec<Object>> = HashMap::new();
+        for object in self.objects_le(&object_keys).await? {
+            by_id.entry(object.id()).or_default().push(object);
+        }
+
+        Ok(keys
+            .iter()
+            .filter_map(|key| {
+                let object = by_id
+                    .get(&key.0)?
+                    .iter()
+                    .filter(|o| u64::from(o.version()) <= key.1)
+                    .max_by_key(|o| o.version())?;
+                Some((*key, object.clone()))
+            })
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl Loader<LiveObjectKey> for PgReader {
+    type Value = StoredLiveness;
+    type Error = Arc<Error>;
+
+    async fn load(
+        &self,
+        keys: &[LiveObjectKey],
+    ) -> Result<HashMap<LiveObjectKey, StoredLiveness>, Self::Error> {
+        use kv_objects::dsl as o;
+        use obj_info::dsl as i;
+
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut conn = self.connect().await.map_err(Arc::new)?;
+
+        // First pass: the latest info record per object. `DISTINCT ON (object_id)` with the
+        // descending version ordering keeps only the newest tombstone state for each object.
+        let ids: Vec<_> = keys.iter().map(|key| key.0.into_bytes()).collect();
+        let info_query = i::obj_info
+            .distinct_on(i::object_id)
+            .order((i::object_id, i::object_version.desc()))
+            .filter(i::object_id.eq_any(&ids));
+
+        let infos: Vec<StoredObjInfo> = conn.results(info_query).await.map_err(Arc::new)?;
+
+        // Second pass: fetch the contents only for objects that are still live, keyed by their
+        // latest version. This keeps the whole batch to two queries rather than one per object.
+        let mut contents_query = o::kv_objects.into_boxed();
+        let mut live_versions: HashMap<&[u8], i64> = HashMap::new();
+        for info in &infos {
+            if info.owner_kind.is_some() {
+                live_versions.insert(&info.object_id[..], info.object_version);
+                contents_query = contents_query.or_filter(
+                    o::object_id
+                        .eq(&info.object_id)
+                        .and(o::object_version.eq(info.object_version)),
+                );
+            }
+        }
+
+        let stored: Vec<StoredObject> = if live_versions.is_empty() {
+            vec![]
+        } else {
+            conn.results(contents_query).await.map_err(Arc::new)?
+        };
+
+        let contents: HashMap<(&[u8], i64), &StoredObject> = stored
+            .iter()
+            .map(|s| ((&s.object_id[..], s.object_version), s))
+            .collect();
+
+        let info_by_id: HashMap<&[u8], &StoredObjInfo> =
+            infos.iter().map(|info| (&info.object_id[..], info)).collect();
+
+        Ok(keys
+            .iter()
+            .filter_map(|key| {
+                let slice: &[u8] = key.0.as_ref();
+                let info = *info_by_id.get(slice)?;
+
+                // No owner on the latest info record means the object is wrapped or deleted, even if
+                // stale contents still exist in `kv_objects`.
+                if info.owner_kind.is_none() {
+                    return Some((*key, StoredLiveness::WrappedOrDeleted));
+                }
+
+                let liveness = match contents.get(&(slice, info.object_version)) {
+                    // A row carrying serialized contents is live; decoding it is deferred to
+                    // [load_live_batch] so a decode error surfaces rather than masquerading as
+                    // pruned. A present row without serialized contents is a pruned tombstone.
+                    Some(stored) if stored.serialized_object.is_some() => {
+                        StoredLiveness::Live((*stored).clone())
+                    }
+                    Some(_) | None => StoredLiveness::Pruned,
+                };
+
+                Some((*key, liveness))
+            })
+            .collect())
+    }
+}
+
+/// Per-backend hit/miss tallies surfaced by [FallbackObjectReader], so operators can observe how
+/// much of each batch was served by the hot path versus the cold fallback.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct BackendStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+/// Result of a composite load: the merged objects keyed as requested, together with per-backend
+/// observability counters.
+pub(crate) struct CompositeObjects {
+    pub objects: HashMap<VersionedObjectKey, Object>,
+    pub primary: BackendStats,
+    pub secondary: BackendStats,
+}
+
+/// A composite `VersionedObjectKey` reader that serves each batch from a primary backend (the hot
+/// Postgres path) and fills any keys it could not satisfy from a secondary backend (e.g. Bigtable
+/// cold storage). The two backends expose different value types (`StoredObject` vs `Object`);
+/// both are normalized to a deserialized `Object` so partial results can be merged.
+///
+/// A key missing from one backend is not an error — it is simply looked up in the other. An error
+/// is only surfaced if *both* backends fail, mirroring a cautious read that refuses to treat a
+/// partial view as authoritative.
+pub(crate) struct FallbackObjectReader {
+    primary: Arc<PgReader>,
+    secondary: Arc<BigtableReader>,
+}
+
+impl FallbackObjectReader {
+    pub(crate) fn new(primary: Arc<PgReader>, secondary: Arc<BigtableReader>) -> Self {
+        Self { primary, secondary }
+    }
+
+    pub(crate) async fn load(
+        &self,
+        keys: &[VersionedObjectKey],
+    ) -> Result<CompositeObjects, Arc<Error>> {
+        if keys.is_empty() {
+            return Ok(CompositeObjects {
+                objects: HashMap::new(),
+                primary: BackendStats::default(),
+                secondary: BackendStats::default(),
+            });
+        }
+
+        // Query the primary. Deserialize its `StoredObject` rows into `Object`s, dropping any that
+        // carry no contents (tombstones) so the secondary gets a chance to serve them.
+        let primary_result = Loader::<VersionedObjectKey>::load(self.primary.as_ref(), keys).await;
+        let mut objects: HashMap<VersionedObjectKey, Object> = match &primary_result {
+            Ok(stored) => stored
+                .iter()
+                .filter_map(|(key, stored)| Some((*key, deserialize_stored(stored).ok()??)))
+                .collect(),
+            Err(_) => HashMap::new(),
+        };
+
+        let primary = BackendStats {
+            hits: objects.len(),
+            misses: keys.len() - objects.len(),
+        };
+
+        // Anything the primary did not resolve is looked up in the secondary.
+        let missing: Vec<VersionedObjectKey> = keys
+            .iter()
+            .filter(|key| !objects.contains_key(key))
+            .copied()
+            .collect();
+
+        let mut secondary = BackendStats::default();
+        if !missing.is_empty() {
+            let secondary_result =
+                Loader::<VersionedObjectKey>::load(self.secondary.as_ref(), &missing).await;
+
+            match secondary_result {
+                Ok(found) => {
+                    secondary.hits = found.len();
+                    secondary.misses = missing.len() - found.len();
+                    objects.extend(found);
+                }
+                Err(err) => {
+                    // Only propagate if the primary also failed; otherwise the primary's partial
+                    // view stands and the secondary's keys are simply treated as misses.
+                    if primary_result.is_err() {
+                        return Err(err);
+                    }
+                    secondary.misses = missing.len();
+                }
+            }
+        }
+
+        // Surface the per-backend split so operators can see how much of each batch the hot path
+        // served versus the cold fallback, and tune which tier holds which version ranges.
+        debug!(
+            primary_hits = primary.hits,
+            primary_misses = primary.misses,
+            secondary_hits = secondary.hits,
+            secondary_misses = secondary.misses,
+            "composite object read",
+        );
+
+        Ok(CompositeObjects {
+            objects,
+            primary,
+            secondary,
+        })
+    }
+}
+
+/// Deserialize a `StoredObject` into an `Object`, returning `None` when the row is a tombstone with
+/// no serialized contents.
+fn deserialize_stored(stored: &StoredObject) -> Result<Option<Object>, anyhow::Error> {
+    let Some(bytes) = &stored.serialized_object else {
+        return Ok(None);
+    };
+    bcs::from_bytes(bytes)
+        .map(Some)
+        .context("Failed to deserialize stored object")
+}
+
 /// Load the contents of an object from the store and deserialize it as an `Object`. This function
 /// does not respect deletion and wrapping. If an object is deleted or wrapped, it may return the
 /// contents of the object before the deletion or wrapping, or it may return `None` if the object
@@ -123,6 +484,62 @@ pub(crate) async fn load_latest(
     Ok(object)
 }
 
+/// Load the latest contents of an object through the tiered [FallbackObjectReader], serving the hot
+/// Postgres path first and filling any miss from the Bigtable cold path. The composite's
+/// per-backend hit/miss tallies are emitted for observability as a side effect of the read. Like
+/// [load_latest], this does not respect deletion and wrapping. This is the entry point the
+/// reader-selection layer uses when an operator has configured tiered object storage.
+pub(crate) async fn load_latest_fallback(
+    ctx: &Context,
+    object_id: ObjectID,
+) -> Result<Option<Object>, anyhow::Error> {
+    let Some(latest_version) = ctx
+        .pg_loader()
+        .load_one(LatestObjectVersionKey(object_id))
+        .await
+        .context("Failed to load latest version")?
+    else {
+        return Ok(None);
+    };
+
+    let reader = FallbackObjectReader::new(ctx.pg_reader(), ctx.bigtable_reader());
+    let key = VersionedObjectKey(object_id, latest_version.object_version as u64);
+
+    let composite = reader
+        .load(&[key])
+        .await
+        .context("Failed composite object load")?;
+
+    Ok(composite.objects.get(&key).cloned())
+}
+
+/// Load the contents of an object as of a historical version ceiling: the greatest version of the
+/// object that is less than or equal to `version_bound`. Like [load_latest], this does not respect
+/// deletion and wrapping, and returns `None` if no version at or below the bound remains in the
+/// store. Downstream resolvers use this to expose object state as it existed at a past checkpoint.
+pub(crate) async fn load_latest_at(
+    ctx: &Context,
+    object_id: ObjectID,
+    version_bound: u64,
+) -> Result<Option<Object>, anyhow::Error> {
+    let Some(bounded) = ctx
+        .pg_loader()
+        .load_one(VersionBoundedObjectKey(object_id, version_bound))
+        .await
+        .context("Failed to load bounded version")?
+    else {
+        return Ok(None);
+    };
+
+    let object = ctx
+        .kv_loader()
+        .load_one_object(object_id, bounded.object_version as u64)
+        .await
+        .context("Failed to load bounded object")?;
+
+    Ok(object)
+}
+
 /// Fetch the latest version of the object at ID `object_id`, and deserialize its contents as a
 /// Rust type `T`, assuming that it is a Move object (not a package). This function does not
 /// respect deletion and wrapping, see [load_latest] for more information.
@@ -163,3 +580,89 @@ pub(crate) async fn load_live(
         "Failed to find content for latest version of live object",
     )?))
 }
+
+/// Resolve the liveness and latest contents of many objects at once, distinguishing live objects
+/// from wrapped/deleted ones and from objects whose contents have been pruned. This batches through
+/// the [LiveObjectKey] loader instead of looping [load_live], so list-style resolvers stay linear in
+/// the number of objects rather than issuing a pair of round-trips per object.
+///
+/// The returned map only contains entries for objects that have an `object_info` record; an ID with
+/// no info at all (one that was never observed) is simply absent from the result.
+pub(crate) async fn load_live_batch(
+    ctx: &Context,
+    ids: impl IntoIterator<Item = ObjectID>,
+) -> Result<HashMap<ObjectID, ObjectLiveness>, anyhow::Error> {
+    let keys: Vec<LiveObjectKey> = ids.into_iter().map(LiveObjectKey).collect();
+
+    let resolved = ctx
+        .pg_loader()
+        .load_many(keys)
+        .await
+        .context("Failed to batch-load object liveness")?;
+
+    resolved
+        .into_iter()
+        .map(|(key, liveness)| {
+            let liveness = match liveness {
+                StoredLiveness::Live(stored) => ObjectLiveness::Live(
+                    deserialize_stored(&stored)?
+                        .context("Live object row carries no contents")?,
+                ),
+                StoredLiveness::WrappedOrDeleted => ObjectLiveness::WrappedOrDeleted,
+                StoredLiveness::Pruned => ObjectLiveness::Pruned,
+            };
+            Ok((key.0, liveness))
+        })
+        .collect()
+}
+
+/// The starting interval between polls in [poll_latest], doubled on each miss.
+const POLL_INTERVAL_MIN: Duration = Duration::from_millis(50);
+
+/// The ceiling on the per-poll backoff interval in [poll_latest].
+const POLL_INTERVAL_MAX: Duration = Duration::from_millis(500);
+
+/// Long-poll for a newer version of an object. `seen_version` is the last `object_version` the
+/// caller observed and is treated as exclusive: the call returns promptly once
+/// [LatestObjectVersionKey] reports a version strictly greater than `seen_version`, loading and
+/// returning those contents. Otherwise it parks, re-checking with an exponentially backing-off
+/// interval (capped by [POLL_INTERVAL_MAX] and by the time left until `timeout`), and returns
+/// `None` if no newer version appears before `timeout` elapses. A returned value always carries
+/// `object_version > seen_version`.
+pub(crate) async fn poll_latest(
+    ctx: &Context,
+    object_id: ObjectID,
+    seen_version: u64,
+    timeout: Duration,
+) -> Result<Option<Object>, anyhow::Error> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut interval = POLL_INTERVAL_MIN;
+
+    // Read the underlying `PgReader` directly rather than through the `DataLoader`: the loader
+    // caches `load_one` by key, so a cached first result would hide the very version bump this loop
+    // exists to observe.
+    let key = LatestObjectVersionKey(object_id);
+    let reader = ctx.pg_loader().loader();
+
+    loop {
+        if let Some(latest) = Loader::<LatestObjectVersionKey>::load(reader, &[key])
+            .await
+            .context("Failed to load latest version")?
+            .remove(&key)
+        {
+            if latest.object_version as u64 > seen_version {
+                return load_latest(ctx, object_id).await;
+            }
+        }
+
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+
+        // Sleep for the backoff interval, but never past the overall deadline.
+        let remaining = deadline - now;
+        tokio::time::sleep(interval.min(remaining)).await;
+        interval = (interval * 2).min(POLL_INTERVAL_MAX);
+    }
+}
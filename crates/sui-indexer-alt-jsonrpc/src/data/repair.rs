@@ -0,0 +1,156 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashSet;
+
+use anyhow::Context as _;
+
+use crate::context::Context;
+
+use super::{object_versions::LatestObjectVersionKey, objects::VersionedObjectKey};
+
+/// How many index entries are confirmed against `kv_objects` per round. Scans run in bounded
+/// batches so a full reconciliation does not hold the whole index in memory or issue an unbounded
+/// query.
+const SCAN_BATCH_SIZE: usize = 1000;
+
+/// Whether a [scan] only reports discrepancy counts or also collects the keys a backfill run should
+/// re-ingest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RepairMode {
+    /// Report discrepancy counts only; [ScanReport::backfilled] is left empty.
+    DryRun,
+    /// Report discrepancies and collect, in [ScanReport::backfilled], the live objects whose
+    /// `kv_objects` rows are missing, so a backfill run can re-ingest their contents. The scan does
+    /// not itself mutate any table.
+    Backfill,
+}
+
+/// The outcome of reconciling a set of latest-version index entries against `kv_objects`.
+///
+/// The counts are deliberately conservative: an entry is only classified once its latest-version
+/// lookup is unambiguous. When that lookup fails or races with a concurrent update it is counted as
+/// [skipped](ScanReport::skipped) rather than flagged as missing, so a transient read gap never
+/// triggers a destructive backfill or report of data loss.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ScanReport {
+    /// Live objects whose latest version's contents are present in `kv_objects`.
+    pub ok: usize,
+    /// Live objects whose latest version's contents are missing or pruned from `kv_objects`.
+    pub missing_content: usize,
+    /// Content rows that no longer correspond to the object's latest version (the index has since
+    /// advanced), i.e. stale contents left behind by a superseded version.
+    pub orphaned_content: usize,
+    /// Entries whose latest-version lookup failed or was ambiguous and were left untouched.
+    pub skipped: usize,
+    /// In [RepairMode::Backfill], the keys with missing contents that a backfill run should
+    /// re-ingest. Empty under [RepairMode::DryRun].
+    pub backfilled: Vec<VersionedObjectKey>,
+}
+
+impl ScanReport {
+    /// Fold another report's counts into this one, so a multi-batch scan can accumulate a single
+    /// summary.
+    fn merge(&mut self, other: ScanReport) {
+        self.ok += other.ok;
+        self.missing_content += other.missing_content;
+        self.orphaned_content += other.orphaned_content;
+        self.skipped += other.skipped;
+        self.backfilled.extend(other.backfilled);
+    }
+}
+
+/// Reconcile latest-version index entries against the contents stored in `kv_objects`, confirming
+/// that each live object's `(object_id, object_version)` row is present.
+///
+/// `entries` are index rows harvested from the `object_versions` table, one [VersionedObjectKey]
+/// per object carrying the version the index believes is latest. Each is confirmed by re-reading
+/// [LatestObjectVersionKey] (to guard against a stale cursor) and probing the contents through the
+/// existing [VersionedObjectKey] loader. Work proceeds in [SCAN_BATCH_SIZE] batches.
+///
+/// The critical invariant, following a repair worker that refuses to treat "not found" as
+/// "deleted", is that an entry whose latest-version lookup errors or returns nothing is skipped
+/// rather than reported as missing — transient read gaps must not drive destructive action.
+pub(crate) async fn scan(
+    ctx: &Context,
+    entries: impl IntoIterator<Item = VersionedObjectKey>,
+    mode: RepairMode,
+) -> Result<ScanReport, anyhow::Error> {
+    let entries: Vec<VersionedObjectKey> = entries.into_iter().collect();
+
+    let mut report = ScanReport::default();
+    for batch in entries.chunks(SCAN_BATCH_SIZE) {
+        report.merge(scan_batch(ctx, batch, mode).await?);
+    }
+
+    Ok(report)
+}
+
+/// Reconcile a single bounded batch of index entries. See [scan] for the classification rules.
+async fn scan_batch(
+    ctx: &Context,
+    batch: &[VersionedObjectKey],
+    mode: RepairMode,
+) -> Result<ScanReport, anyhow::Error> {
+    // Re-read the authoritative latest version for each object. A batch-level failure here is a
+    // transient read gap: skip the whole batch rather than risk flagging live objects as missing.
+    let latest = match ctx
+        .pg_loader()
+        .load_many(batch.iter().map(|key| LatestObjectVersionKey(key.0)))
+        .await
+    {
+        Ok(latest) => latest,
+        Err(_) => {
+            return Ok(ScanReport {
+                skipped: batch.len(),
+                ..Default::default()
+            });
+        }
+    };
+
+    // Probe the contents for each candidate version in one batched round-trip.
+    let contents = ctx
+        .pg_loader()
+        .load_many(batch.iter().copied())
+        .await
+        .context("Failed to probe object contents")?;
+
+    let mut report = ScanReport::default();
+    let mut to_backfill: HashSet<VersionedObjectKey> = HashSet::new();
+
+    for key in batch {
+        let Some(current) = latest.get(&LatestObjectVersionKey(key.0)) else {
+            // The index entry vanished between harvest and confirmation (or the lookup was
+            // ambiguous). Skip rather than assume the object was deleted.
+            report.skipped += 1;
+            continue;
+        };
+
+        // A row without serialized contents is a pruned tombstone, not present contents.
+        let has_contents = contents
+            .get(key)
+            .is_some_and(|stored| stored.serialized_object.is_some());
+
+        let current_version = current.object_version as u64;
+        if current_version != key.1 {
+            // The index has advanced past the harvested version; any contents still stored for it
+            // are stale contents left behind by a superseded version.
+            if has_contents {
+                report.orphaned_content += 1;
+            }
+            continue;
+        }
+
+        if has_contents {
+            report.ok += 1;
+        } else {
+            report.missing_content += 1;
+            if mode == RepairMode::Backfill {
+                to_backfill.insert(*key);
+            }
+        }
+    }
+
+    report.backfilled = to_backfill.into_iter().collect();
+    Ok(report)
+}